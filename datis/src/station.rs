@@ -1,6 +1,9 @@
 use crate::error::Error;
+use crate::units::{PressureUnit, Units};
 use crate::utils::{pronounce_number, round};
-use crate::weather::{DynamicWeather, StaticWeather, WeatherInfo, WeatherKind};
+use crate::weather::{
+    self, CloudLayer, DynamicWeather, OpenMeteoWeather, StaticWeather, WeatherInfo, WeatherKind,
+};
 use crate::tts::VoiceKind;
 
 #[derive(Debug, Clone)]
@@ -13,6 +16,12 @@ pub struct Station {
     pub weather_kind: WeatherKind,
     pub static_weather: StaticWeather,
     pub dynamic_weather: DynamicWeather,
+    /// a raw METAR observation string, used when `weather_kind` is `WeatherKind::Metar`
+    pub metar: Option<String>,
+    /// used when `weather_kind` is `WeatherKind::OpenMeteo`
+    pub open_meteo_weather: Option<OpenMeteoWeather>,
+    /// units the spoken report is voiced in
+    pub units: Units,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -31,38 +40,125 @@ pub struct Airfield {
     pub runways: Vec<String>,
 }
 
+/// The data a station's spoken report is built from, in its configured units.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportData {
+    pub information_letter: String,
+    pub active_runway: Option<String>,
+    pub wind_dir: f64, // in degrees
+    pub wind_speed: f64,
+    pub wind_speed_unit: &'static str,
+    pub wind_gust: Option<f64>,
+    pub temperature: f64,
+    pub temperature_unit: &'static str,
+    pub altimeter: f64,
+    pub altimeter_unit: &'static str,
+    pub altimeter_in_hg: f64,
+    pub qnh_hpa: f64,
+    pub visibility: Option<f64>, // in meters
+    pub clouds: Vec<CloudLayer>,
+    pub ceiling: Option<CloudLayer>,
+    pub atis_freq: u64,
+    pub traffic_freq: Option<u64>,
+}
+
 impl Station {
-    pub fn generate_report(&self, report_nr: usize) -> Result<String, Error> {
+    /// Extracts the structured data a report is made of, without voicing it.
+    pub fn report_data(&self, report_nr: usize) -> Result<ReportData, Error> {
         let information_letter = PHONETIC_ALPHABET[report_nr % PHONETIC_ALPHABET.len()];
-
         let weather = self.get_current_weather()?;
-        let mut report = format!("This is {} information {}. ", self.name, information_letter);
 
-        if let Some(rwy) = self.get_active_runway(weather.wind_dir) {
-            let rwy = pronounce_number(rwy);
-            report += &format!("Runway in use is {}. ", rwy);
+        let active_runway = self
+            .get_active_runway(weather.wind_dir.to_degrees(), weather.wind_speed)
+            .map(String::from);
+
+        // only surface a gust that meaningfully exceeds the steady wind
+        let wind_gust = weather
+            .wind_gust
+            .filter(|gust| gust - weather.wind_speed >= 5.0 / 1.94384)
+            .map(|gust| self.units.wind.convert(gust));
+
+        Ok(ReportData {
+            information_letter: information_letter.to_string(),
+            active_runway,
+            wind_dir: weather.wind_dir.to_degrees().round(),
+            wind_speed: self.units.wind.convert(weather.wind_speed),
+            wind_speed_unit: self.units.wind.spoken_suffix(),
+            wind_gust,
+            temperature: self.units.temperature.convert(weather.temperature),
+            temperature_unit: self.units.temperature.spoken_suffix(),
+            altimeter: self.units.pressure.convert(weather.pressure),
+            altimeter_unit: self.units.pressure.spoken_label(),
+            altimeter_in_hg: PressureUnit::InHg.convert(weather.pressure),
+            qnh_hpa: PressureUnit::HPa.convert(weather.pressure),
+            visibility: weather.visibility,
+            ceiling: weather::ceiling(&weather.clouds).copied(),
+            clouds: weather.clouds,
+            atis_freq: self.atis_freq,
+            traffic_freq: self.traffic_freq,
+        })
+    }
+
+    /// Renders [`Station::report_data`] as JSON.
+    pub fn report_data_json(&self, report_nr: usize) -> Result<String, Error> {
+        Ok(serde_json::to_string(&self.report_data(report_nr)?)?)
+    }
+
+    pub fn generate_report(&self, report_nr: usize) -> Result<String, Error> {
+        let data = self.report_data(report_nr)?;
+
+        let mut report = format!(
+            "This is {} information {}. ",
+            self.name, data.information_letter
+        );
+
+        if let Some(rwy) = &data.active_runway {
+            report += &format!("Runway in use is {}. ", pronounce_number(rwy.as_str()));
         } else {
             error!("Could not find active runway for {}", self.name);
         }
 
-        let wind_dir = format!("{:0>3}", weather.wind_dir.to_degrees().round().to_string());
+        let wind_dir = format!("{:0>3}", data.wind_dir.to_string());
         report += &format!(
-            "Wind {} at {} knots. ",
+            "Wind {} at {} {}",
             pronounce_number(wind_dir),
-            pronounce_number((weather.wind_speed * 1.94384).round()), // to knots
+            pronounce_number(data.wind_speed.round()),
+            data.wind_speed_unit,
         );
 
-        if self.weather_kind == WeatherKind::Static {
-            report += &format!("{}. ", self.static_weather.get_clouds_report());
+        if let Some(wind_gust) = data.wind_gust {
+            report += &format!(
+                " gusting to {} {}",
+                pronounce_number(wind_gust.round()),
+                data.wind_speed_unit,
+            );
+        }
+
+        report += ". ";
+
+        if let Some(visibility) = data.visibility {
+            report += &format!("Visibility {} meters. ", pronounce_number(visibility.round()));
+        }
+
+        report += &format!("{}. ", weather::describe_clouds(&data.clouds));
+
+        if let Some(ceiling) = &data.ceiling {
+            report += &format!(
+                "Ceiling {} feet. ",
+                pronounce_number((ceiling.base * 3.28084).round()),
+            );
         }
 
         report += &format!(
-            "Temperature {} celcius, ALTIMETER {}. ",
-            pronounce_number(round(weather.temperature, 1)),
-            pronounce_number(round(weather.pressure * 0.0002953, 2)), // inHg
+            "Temperature {} {}, {} {}{}. ",
+            pronounce_number(round(data.temperature, 1)),
+            data.temperature_unit,
+            data.altimeter_unit,
+            pronounce_number(round(data.altimeter, self.units.pressure.precision())),
+            self.units.pressure.spoken_suffix(),
         );
 
-        if let Some(traffic_freq) = self.traffic_freq {
+        if let Some(traffic_freq) = data.traffic_freq {
             report += &format!(
                 "Traffic frequency {}. ",
                 pronounce_number(round(traffic_freq as f64 / 1_000_000.0, 3))
@@ -71,10 +167,10 @@ impl Station {
 
         report += &format!(
             "REMARKS {} hectopascal. ",
-            pronounce_number((weather.pressure / 100.0).round()), // to hPA
+            pronounce_number(data.qnh_hpa.round()),
         );
 
-        report += &format!("End information {}. ", information_letter);
+        report += &format!("End information {}. ", data.information_letter);
 
         Ok(report)
     }
@@ -84,13 +180,35 @@ impl Station {
         Ok(WeatherInfo {
             wind_speed: 5.0,
             wind_dir: (330.0f64).to_radians(),
+            wind_gust: self.static_weather.wind.gust,
             temperature: 22.0,
             pressure: 101500.0,
+            clouds: Vec::new(),
+            visibility: Some(10_000.0),
         })
     }
 
     #[cfg(not(test))]
     fn get_current_weather(&self) -> Result<WeatherInfo, Error> {
+        if self.weather_kind == WeatherKind::Metar {
+            let raw = self.metar.as_deref().unwrap_or_default();
+            return weather::parse_metar(raw);
+        }
+
+        if self.weather_kind == WeatherKind::OpenMeteo {
+            if let Some(open_meteo_weather) = &self.open_meteo_weather {
+                match open_meteo_weather
+                    .get_at(self.airfield.position.x, self.airfield.position.y)
+                {
+                    Ok(info) => return Ok(info),
+                    Err(err) => error!(
+                        "Error fetching Open-Meteo weather for {}, falling back to dynamic weather: {}",
+                        self.name, err
+                    ),
+                }
+            }
+        }
+
         let mut info = self.dynamic_weather.get_at(
             self.airfield.position.x,
             self.airfield.position.y,
@@ -100,26 +218,40 @@ impl Station {
         if self.weather_kind == WeatherKind::Static {
             info.wind_speed = self.static_weather.wind.speed;
             info.wind_dir = self.static_weather.wind.dir;
+            info.wind_gust = self.static_weather.wind.gust;
+            info.clouds = self.static_weather.clouds.clone();
         }
 
         Ok(info)
     }
 
-    fn get_active_runway(&self, wind_dir: f64) -> Option<&str> {
-        for rwy in &self.airfield.runways {
-            if let Ok(mut rwy_dir) = rwy.parse::<f64>() {
-                rwy_dir *= 10.0; // e.g. 04 to 040
-                let phi = (wind_dir - rwy_dir).abs() % 360.0;
-                let distance = if phi > 180.0 { 360.0 - phi } else { phi };
-                if distance <= 90.0 {
-                    return Some(&rwy);
-                }
-            } else {
-                error!("Error parsing runway: {}", rwy);
-            }
+    /// Picks the runway with the greatest headwind component. Falls back to
+    /// the first configured runway when the wind is too calm to favor one.
+    fn get_active_runway(&self, wind_dir: f64, wind_speed: f64) -> Option<&str> {
+        const CALM_WIND_THRESHOLD: f64 = 0.5; // m/s
+
+        if wind_speed < CALM_WIND_THRESHOLD {
+            return self.airfield.runways.first().map(String::as_str);
         }
 
-        None
+        self.airfield
+            .runways
+            .iter()
+            .filter_map(|rwy| match rwy.parse::<f64>() {
+                Ok(heading) => Some((rwy, heading * 10.0)), // e.g. 04 to 040
+                Err(_) => {
+                    error!("Error parsing runway: {}", rwy);
+                    None
+                }
+            })
+            .map(|(rwy, rwy_dir)| {
+                let delta = (wind_dir - rwy_dir).abs() % 360.0;
+                let delta = if delta > 180.0 { 360.0 - delta } else { delta };
+                let headwind = wind_speed * delta.to_radians().cos();
+                (rwy, headwind)
+            })
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .map(|(rwy, _)| rwy.as_str())
     }
 }
 
@@ -132,6 +264,7 @@ static PHONETIC_ALPHABET: &'static [&str] = &[
 #[cfg(test)]
 mod test {
     use super::{Airfield, Position, Station};
+    use crate::units::Units;
     use crate::weather::{DynamicWeather, StaticWeather, WeatherKind};
     use crate::tts::VoiceKind;
 
@@ -154,16 +287,22 @@ mod test {
             weather_kind: WeatherKind::Static,
             static_weather: StaticWeather::default(),
             dynamic_weather: DynamicWeather::create("").unwrap(),
+            metar: None,
+            open_meteo_weather: None,
+            units: Units::default(),
         };
 
-        assert_eq!(station.get_active_runway(0.0), Some("04"));
-        assert_eq!(station.get_active_runway(30.0), Some("04"));
-        assert_eq!(station.get_active_runway(129.0), Some("04"));
-        assert_eq!(station.get_active_runway(311.0), Some("04"));
-        assert_eq!(station.get_active_runway(180.0), Some("22"));
-        assert_eq!(station.get_active_runway(270.0), Some("22"));
-        assert_eq!(station.get_active_runway(309.0), Some("22"));
-        assert_eq!(station.get_active_runway(131.0), Some("22"));
+        assert_eq!(station.get_active_runway(0.0, 10.0), Some("04"));
+        assert_eq!(station.get_active_runway(30.0, 10.0), Some("04"));
+        assert_eq!(station.get_active_runway(129.0, 10.0), Some("04"));
+        assert_eq!(station.get_active_runway(311.0, 10.0), Some("04"));
+        assert_eq!(station.get_active_runway(180.0, 10.0), Some("22"));
+        assert_eq!(station.get_active_runway(270.0, 10.0), Some("22"));
+        assert_eq!(station.get_active_runway(309.0, 10.0), Some("22"));
+        assert_eq!(station.get_active_runway(131.0, 10.0), Some("22"));
+
+        // below the calm-wind threshold, fall back to the first configured runway
+        assert_eq!(station.get_active_runway(180.0, 0.1), Some("04"));
     }
 
     #[test]
@@ -185,9 +324,83 @@ mod test {
             weather_kind: WeatherKind::Static,
             static_weather: StaticWeather::default(),
             dynamic_weather: DynamicWeather::create("").unwrap(),
+            metar: None,
+            open_meteo_weather: None,
+            units: Units::default(),
         };
 
         let report = station.generate_report(26).unwrap();
-        assert_eq!(report, r"This is Kutaisi information Alpha. Runway in use is 0 4. Wind 3 3 0 at 1 0 knots. Visibility 0. Temperature 2 2 celcius, ALTIMETER 2 NINER DECIMAL NINER 7. Traffic frequency 2 4 NINER DECIMAL 5. REMARKS 1 0 1 5 hectopascal. End information Alpha. ");
+        assert_eq!(report, r"This is Kutaisi information Alpha. Runway in use is 0 4. Wind 3 3 0 at 1 0 knots. Visibility 1 0 0 0 0 meters. Sky clear. Temperature 2 2 celcius, ALTIMETER 2 NINER DECIMAL NINER 7. Traffic frequency 2 4 NINER DECIMAL 5. REMARKS 1 0 1 5 hectopascal. End information Alpha. ");
+    }
+
+    fn test_station() -> Station {
+        Station {
+            name: String::from("Kutaisi"),
+            atis_freq: 251_000_000,
+            traffic_freq: Some(249_500_000),
+            voice: VoiceKind::StandardC,
+            airfield: Airfield {
+                name: String::from("Kutaisi"),
+                position: Position {
+                    x: 0.0,
+                    y: 0.0,
+                    alt: 0.0,
+                },
+                runways: vec![String::from("04"), String::from("22")],
+            },
+            weather_kind: WeatherKind::Static,
+            static_weather: StaticWeather::default(),
+            dynamic_weather: DynamicWeather::create("").unwrap(),
+            metar: None,
+            open_meteo_weather: None,
+            units: Units::default(),
+        }
+    }
+
+    #[test]
+    fn test_report_voices_gust_above_threshold() {
+        let mut station = test_station();
+        station.static_weather.wind.gust = Some(10.0); // 5 m/s over the 5 m/s steady wind
+
+        let report = station.generate_report(26).unwrap();
+        assert!(report.contains("gusting to 1 9 knots"));
+    }
+
+    #[test]
+    fn test_report_suppresses_gust_below_threshold() {
+        let mut station = test_station();
+        station.static_weather.wind.gust = Some(6.0); // less than 5 kt over the steady wind
+
+        let report = station.generate_report(26).unwrap();
+        assert!(!report.contains("gusting"));
+    }
+
+    #[test]
+    fn test_report_data() {
+        let data = test_station().report_data(26).unwrap();
+
+        assert_eq!(data.information_letter, "Alpha");
+        assert_eq!(data.active_runway, Some(String::from("04")));
+        assert_eq!(data.wind_dir, 330.0);
+        assert!((data.wind_speed - 9.7192).abs() < 0.001);
+        assert_eq!(data.wind_gust, None);
+        assert_eq!(data.temperature, 22.0);
+        assert!((data.altimeter_in_hg - 29.97).abs() < 0.01);
+        assert_eq!(data.qnh_hpa, 1015.0);
+        assert_eq!(data.visibility, Some(10_000.0));
+        assert_eq!(data.ceiling, None);
+        assert!(data.clouds.is_empty());
+        assert_eq!(data.atis_freq, 251_000_000);
+        assert_eq!(data.traffic_freq, Some(249_500_000));
+    }
+
+    #[test]
+    fn test_report_data_json() {
+        let json = test_station().report_data_json(26).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["information_letter"], "Alpha");
+        assert_eq!(value["qnh_hpa"], 1015.0);
+        assert_eq!(value["visibility"], 10_000.0);
     }
 }
@@ -0,0 +1,121 @@
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    pub fn convert(self, celsius: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => celsius,
+            TemperatureUnit::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    pub fn spoken_suffix(self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "celcius",
+            TemperatureUnit::Fahrenheit => "fahrenheit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PressureUnit {
+    #[default]
+    InHg,
+    HPa,
+    MmHg,
+}
+
+impl PressureUnit {
+    pub fn convert(self, pascal: f64) -> f64 {
+        match self {
+            PressureUnit::InHg => pascal * 0.0002953,
+            PressureUnit::HPa => pascal / 100.0,
+            PressureUnit::MmHg => pascal * 0.00750062,
+        }
+    }
+
+    /// The label the value is announced under, matching how each unit is
+    /// conventionally read out on ATIS ("ALTIMETER" for inHg, "QNH" otherwise).
+    pub fn spoken_label(self) -> &'static str {
+        match self {
+            PressureUnit::InHg => "ALTIMETER",
+            PressureUnit::HPa | PressureUnit::MmHg => "QNH",
+        }
+    }
+
+    pub fn spoken_suffix(self) -> &'static str {
+        match self {
+            PressureUnit::InHg => "",
+            PressureUnit::HPa => " hectopascal",
+            PressureUnit::MmHg => " millimeters of mercury",
+        }
+    }
+
+    /// Decimal places the value is rounded to before being voiced.
+    pub fn precision(self) -> i32 {
+        match self {
+            PressureUnit::InHg => 2,
+            PressureUnit::HPa | PressureUnit::MmHg => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WindUnit {
+    #[default]
+    Knots,
+    MetersPerSecond,
+    KilometersPerHour,
+    MilesPerHour,
+}
+
+impl WindUnit {
+    pub fn convert(self, meters_per_second: f64) -> f64 {
+        match self {
+            WindUnit::Knots => meters_per_second * 1.94384,
+            WindUnit::MetersPerSecond => meters_per_second,
+            WindUnit::KilometersPerHour => meters_per_second * 3.6,
+            WindUnit::MilesPerHour => meters_per_second * 2.23694,
+        }
+    }
+
+    pub fn spoken_suffix(self) -> &'static str {
+        match self {
+            WindUnit::Knots => "knots",
+            WindUnit::MetersPerSecond => "meters per second",
+            WindUnit::KilometersPerHour => "kilometers per hour",
+            WindUnit::MilesPerHour => "miles per hour",
+        }
+    }
+}
+
+/// The units a station's spoken report is voiced in. Defaults match the
+/// conventional US ATIS format (Celsius, inHg, knots).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Units {
+    pub temperature: TemperatureUnit,
+    pub pressure: PressureUnit,
+    pub wind: WindUnit,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pressure_conversions() {
+        assert!((PressureUnit::InHg.convert(101_500.0) - 29.97).abs() < 0.01);
+        assert_eq!(PressureUnit::HPa.convert(101_500.0), 1015.0);
+        assert!((PressureUnit::MmHg.convert(101_500.0) - 761.3).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_wind_conversions() {
+        assert!((WindUnit::Knots.convert(10.0) - 19.4384).abs() < 0.001);
+        assert_eq!(WindUnit::MetersPerSecond.convert(10.0), 10.0);
+    }
+}
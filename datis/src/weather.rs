@@ -0,0 +1,481 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::error::Error;
+use crate::utils::pronounce_number;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeatherKind {
+    Static,
+    Dynamic,
+    /// weather is ingested from a real-world METAR observation string
+    Metar,
+    /// weather is fetched from the Open-Meteo API for the airfield's real-world location
+    OpenMeteo,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Wind {
+    pub speed: f64, // in m/s
+    pub dir: f64,   // in radians
+    pub gust: Option<f64>, // in m/s
+}
+
+/// METAR-style coverage categories, ordered from least to most overcast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum CloudCoverage {
+    Clear,
+    Few,
+    Scattered,
+    Broken,
+    Overcast,
+}
+
+impl CloudCoverage {
+    /// Maps a coverage fraction in oktas (eighths of sky covered) to the
+    /// METAR coverage category: 0 clear/CAVOK, 1-2 FEW, 3-4 SCT, 5-7 BKN, 8 OVC.
+    pub fn from_oktas(oktas: u8) -> Self {
+        match oktas {
+            0 => CloudCoverage::Clear,
+            1..=2 => CloudCoverage::Few,
+            3..=4 => CloudCoverage::Scattered,
+            5..=7 => CloudCoverage::Broken,
+            _ => CloudCoverage::Overcast,
+        }
+    }
+
+    /// A ceiling is formed by a broken or overcast layer.
+    fn is_ceiling(self) -> bool {
+        matches!(self, CloudCoverage::Broken | CloudCoverage::Overcast)
+    }
+
+    fn spoken(self) -> &'static str {
+        match self {
+            CloudCoverage::Clear => "Clear",
+            CloudCoverage::Few => "Few",
+            CloudCoverage::Scattered => "Scattered",
+            CloudCoverage::Broken => "Broken",
+            CloudCoverage::Overcast => "Overcast",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CloudLayer {
+    pub coverage: CloudCoverage,
+    pub base: f64, // in meters
+}
+
+impl CloudLayer {
+    /// Builds a layer from an okta-based cloud density, the representation
+    /// mission authors typically work in, mapping it to a METAR coverage
+    /// category via [`CloudCoverage::from_oktas`].
+    pub fn from_oktas(oktas: u8, base: f64) -> Self {
+        CloudLayer {
+            coverage: CloudCoverage::from_oktas(oktas),
+            base,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StaticWeather {
+    pub wind: Wind,
+    pub clouds: Vec<CloudLayer>,
+    pub qnh: f64, // in Pa
+}
+
+impl StaticWeather {
+    /// Adds a cloud layer described by its okta-based density, as a mission
+    /// author would configure it, rather than a pre-decided coverage category.
+    pub fn add_cloud_layer(&mut self, oktas: u8, base: f64) {
+        self.clouds.push(CloudLayer::from_oktas(oktas, base));
+    }
+}
+
+/// Voices a station's cloud layers as cumulative ATIS-style groups, e.g.
+/// "Few at 3 0 0 0 feet, Broken at 1 0 0 0 0 feet", lowest layer first.
+pub fn describe_clouds(clouds: &[CloudLayer]) -> String {
+    let mut layers: Vec<&CloudLayer> = clouds
+        .iter()
+        .filter(|layer| layer.coverage != CloudCoverage::Clear)
+        .collect();
+
+    if layers.is_empty() {
+        return String::from("Sky clear");
+    }
+
+    layers.sort_by(|a, b| a.base.partial_cmp(&b.base).unwrap());
+
+    layers
+        .iter()
+        .map(|layer| {
+            format!(
+                "{} at {} feet",
+                layer.coverage.spoken(),
+                pronounce_number((layer.base * 3.28084).round()), // meters to feet
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns the lowest broken-or-overcast layer, i.e. the ceiling.
+pub fn ceiling(clouds: &[CloudLayer]) -> Option<&CloudLayer> {
+    clouds
+        .iter()
+        .filter(|layer| layer.coverage.is_ceiling())
+        .min_by(|a, b| a.base.partial_cmp(&b.base).unwrap())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DynamicWeather {
+    mission_path: Option<String>,
+}
+
+impl DynamicWeather {
+    pub fn create(mission_path: &str) -> Result<Self, Error> {
+        if mission_path.is_empty() {
+            return Ok(DynamicWeather { mission_path: None });
+        }
+
+        Ok(DynamicWeather {
+            mission_path: Some(mission_path.to_string()),
+        })
+    }
+
+    pub fn get_at(&self, _x: f64, _y: f64, _alt: f64) -> Result<WeatherInfo, Error> {
+        // without a mission loaded, fall back to an ISA standard day
+        Ok(WeatherInfo {
+            wind_speed: 0.0,
+            wind_dir: 0.0,
+            wind_gust: None,
+            temperature: 15.0,
+            pressure: 101_325.0,
+            clouds: Vec::new(),
+            visibility: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WeatherInfo {
+    pub wind_speed: f64, // in m/s
+    pub wind_dir: f64,   // in radians
+    pub wind_gust: Option<f64>, // in m/s
+    pub temperature: f64, // in celsius
+    pub pressure: f64,   // in Pa
+    pub clouds: Vec<CloudLayer>,
+    pub visibility: Option<f64>, // in meters
+}
+
+const DEFAULT_CACHE_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Fetches live weather for an airfield's real-world location from the free
+/// Open-Meteo forecast API's `current` conditions, caching the response for
+/// `cache_interval` so a busy ATIS station doesn't re-poll on every report.
+#[derive(Debug, Clone)]
+pub struct OpenMeteoWeather {
+    origin_lat: f64,
+    origin_lon: f64,
+    cache_interval: Duration,
+    cache: Arc<Mutex<Option<(Instant, WeatherInfo)>>>,
+}
+
+impl OpenMeteoWeather {
+    /// `origin_lat`/`origin_lon` are the real-world coordinates of the DCS
+    /// map's `(0, 0)` point, used to locate the airfield for the API call.
+    pub fn create(origin_lat: f64, origin_lon: f64) -> Self {
+        OpenMeteoWeather::with_cache_interval(origin_lat, origin_lon, DEFAULT_CACHE_INTERVAL)
+    }
+
+    pub fn with_cache_interval(origin_lat: f64, origin_lon: f64, cache_interval: Duration) -> Self {
+        OpenMeteoWeather {
+            origin_lat,
+            origin_lon,
+            cache_interval,
+            cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the current weather at the DCS map position `(x, y)`, using a
+    /// cached response if it is still fresh.
+    pub fn get_at(&self, x: f64, y: f64) -> Result<WeatherInfo, Error> {
+        let mut cache = self.cache.lock().unwrap();
+
+        if let Some((fetched_at, info)) = cache.clone() {
+            if fetched_at.elapsed() < self.cache_interval {
+                return Ok(info);
+            }
+        }
+
+        let (lat, lon) = dcs_to_latlon(x, y, self.origin_lat, self.origin_lon);
+        let info = fetch_current_weather(lat, lon)?;
+        *cache = Some((Instant::now(), info.clone()));
+
+        Ok(info)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoResponse {
+    current: OpenMeteoCurrent,
+}
+
+// `current_weather=true` does not return a pressure reading at all, so we
+// request the individual fields we need via `current=...` instead.
+#[derive(Debug, Deserialize)]
+struct OpenMeteoCurrent {
+    temperature_2m: f64,     // in celsius, since we request metric units
+    wind_speed_10m: f64,     // in m/s, since we request that unit explicitly
+    wind_direction_10m: f64, // in degrees
+    surface_pressure: f64,   // in hPa
+}
+
+fn fetch_current_weather(lat: f64, lon: f64) -> Result<WeatherInfo, Error> {
+    let url = format!(
+        "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&current=temperature_2m,wind_speed_10m,wind_direction_10m,surface_pressure&wind_speed_unit=ms&temperature_unit=celsius",
+        lat, lon
+    );
+
+    let res: OpenMeteoResponse = reqwest::blocking::get(&url)?.json()?;
+    let current = res.current;
+
+    Ok(WeatherInfo {
+        wind_speed: current.wind_speed_10m,
+        wind_dir: current.wind_direction_10m.to_radians(),
+        wind_gust: None, // Open-Meteo's current endpoint does not report gusts
+        temperature: current.temperature_2m,
+        pressure: current.surface_pressure * 100.0, // hPa to Pa
+        clouds: Vec::new(), // Open-Meteo's current endpoint does not report cloud layers
+        visibility: None, // Open-Meteo's current endpoint does not report visibility
+    })
+}
+
+/// Converts a DCS mission's flat map `x`/`y` coordinates to a real-world
+/// `(lat, lon)` pair. DCS maps use a Transverse Mercator projection around a
+/// map-specific origin; `origin_lat`/`origin_lon` should be the real-world
+/// coordinates of the map's `(0, 0)` point. This function does not invert
+/// that projection: it offsets the origin by a flat meters-per-degree
+/// approximation, so its error grows with distance from the origin.
+fn dcs_to_latlon(x: f64, y: f64, origin_lat: f64, origin_lon: f64) -> (f64, f64) {
+    const METERS_PER_DEGREE_LAT: f64 = 111_320.0;
+
+    let lat = origin_lat + x / METERS_PER_DEGREE_LAT;
+    let meters_per_degree_lon = METERS_PER_DEGREE_LAT * lat.to_radians().cos();
+    let lon = origin_lon + y / meters_per_degree_lon;
+
+    (lat, lon)
+}
+
+/// Parses a standard METAR observation string (e.g.
+/// `UGKO 281200Z 33010G18KT 9999 FEW030 SCT100 22/15 Q1015`) into a
+/// [`WeatherInfo`]. Unrecognized or missing groups are skipped rather than
+/// failing the whole station, since real-world METARs vary in how many
+/// optional groups they include.
+pub fn parse_metar(raw: &str) -> Result<WeatherInfo, Error> {
+    let mut info = WeatherInfo::default();
+
+    for group in raw.split_whitespace() {
+        if let Some(wind) = parse_wind_group(group) {
+            info.wind_speed = wind.speed;
+            info.wind_dir = wind.dir;
+            info.wind_gust = wind.gust;
+        } else if let Some((temperature, _dewpoint)) = parse_temperature_group(group) {
+            info.temperature = temperature;
+        } else if let Some(pressure) = parse_altimeter_group(group) {
+            info.pressure = pressure;
+        } else if let Some(layer) = parse_cloud_group(group) {
+            info.clouds.push(layer);
+        } else if let Some(visibility) = parse_visibility_group(group) {
+            info.visibility = Some(visibility);
+        }
+    }
+
+    Ok(info)
+}
+
+fn parse_wind_group(group: &str) -> Option<Wind> {
+    let group = group.strip_suffix("KT")?;
+
+    if group.len() < 5 {
+        return None;
+    }
+
+    let (dir_str, rest) = group.split_at(3);
+    let dir = if dir_str == "VRB" {
+        0.0
+    } else {
+        dir_str.parse::<f64>().ok()?.to_radians()
+    };
+
+    let speed_str: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let speed = speed_str.parse::<f64>().ok()? / 1.94384; // knots to m/s
+
+    let gust = rest
+        .split_once('G')
+        .and_then(|(_, gust_str)| gust_str.parse::<f64>().ok())
+        .map(|gust_kt| gust_kt / 1.94384); // knots to m/s
+
+    Some(Wind { speed, dir, gust })
+}
+
+fn parse_temperature_group(group: &str) -> Option<(f64, f64)> {
+    let (temp_str, dew_str) = group.split_once('/')?;
+
+    if temp_str.is_empty() || dew_str.is_empty() {
+        return None;
+    }
+
+    let temperature = parse_signed_temperature(temp_str)?;
+    let dewpoint = parse_signed_temperature(dew_str)?;
+
+    Some((temperature, dewpoint))
+}
+
+fn parse_signed_temperature(value: &str) -> Option<f64> {
+    if let Some(value) = value.strip_prefix('M') {
+        Some(-value.parse::<f64>().ok()?)
+    } else {
+        value.parse::<f64>().ok()
+    }
+}
+
+fn parse_altimeter_group(group: &str) -> Option<f64> {
+    if let Some(hpa) = group.strip_prefix('Q') {
+        let hpa = hpa.parse::<f64>().ok()?;
+        Some(hpa * 100.0) // hPa to Pa
+    } else if let Some(in_hg) = group.strip_prefix('A') {
+        let in_hg = in_hg.parse::<f64>().ok()? / 100.0;
+        Some(in_hg / 0.0002953) // inHg to Pa
+    } else {
+        None
+    }
+}
+
+fn parse_cloud_group(group: &str) -> Option<CloudLayer> {
+    if matches!(group, "CLR" | "SKC" | "NSC") {
+        return Some(CloudLayer {
+            coverage: CloudCoverage::Clear,
+            base: 0.0,
+        });
+    }
+
+    if group.len() != 6 {
+        return None;
+    }
+
+    let (coverage_str, height_str) = group.split_at(3);
+    let coverage = match coverage_str {
+        "FEW" => CloudCoverage::Few,
+        "SCT" => CloudCoverage::Scattered,
+        "BKN" => CloudCoverage::Broken,
+        "OVC" => CloudCoverage::Overcast,
+        _ => return None,
+    };
+
+    let height_hundreds_ft = height_str.parse::<f64>().ok()?;
+    let base = height_hundreds_ft * 100.0 / 3.28084; // hundreds of feet to meters
+
+    Some(CloudLayer { coverage, base })
+}
+
+/// Parses the 4-digit prevailing visibility group in meters, e.g. `9999`
+/// (METAR's convention for "10 km or more").
+fn parse_visibility_group(group: &str) -> Option<f64> {
+    if group.len() != 4 || !group.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let meters = group.parse::<f64>().ok()?;
+
+    Some(if meters == 9999.0 { 10_000.0 } else { meters })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_open_meteo_response_deserializes_surface_pressure() {
+        // a real `current=temperature_2m,wind_speed_10m,wind_direction_10m,surface_pressure`
+        // response does not nest these fields under `current_weather`, and has no `pressure` field
+        let body = r#"{"current":{"temperature_2m":18.4,"wind_speed_10m":6.2,"wind_direction_10m":250.0,"surface_pressure":1009.3}}"#;
+        let res: OpenMeteoResponse = serde_json::from_str(body).unwrap();
+
+        assert_eq!(res.current.temperature_2m, 18.4);
+        assert_eq!(res.current.wind_speed_10m, 6.2);
+        assert_eq!(res.current.wind_direction_10m, 250.0);
+        assert_eq!(res.current.surface_pressure, 1009.3);
+    }
+
+    #[test]
+    fn test_parse_metar() {
+        let info = parse_metar("UGKO 281200Z 33010G18KT 9999 FEW030 SCT100 22/15 Q1015").unwrap();
+
+        assert_eq!(info.wind_dir, (330.0f64).to_radians());
+        assert!((info.wind_speed - 10.0 / 1.94384).abs() < 0.001);
+        assert!((info.wind_gust.unwrap() - 18.0 / 1.94384).abs() < 0.001);
+        assert_eq!(info.temperature, 22.0);
+        assert_eq!(info.pressure, 101_500.0);
+        assert_eq!(info.clouds.len(), 2);
+        assert_eq!(info.clouds[0].coverage, CloudCoverage::Few);
+        assert_eq!(info.clouds[1].coverage, CloudCoverage::Scattered);
+        assert_eq!(info.visibility, Some(10_000.0));
+    }
+
+    #[test]
+    fn test_static_weather_add_cloud_layer_from_oktas() {
+        let mut weather = StaticWeather::default();
+        weather.add_cloud_layer(6, 1_000.0);
+
+        assert_eq!(weather.clouds.len(), 1);
+        assert_eq!(weather.clouds[0].coverage, CloudCoverage::Broken);
+        assert_eq!(weather.clouds[0].base, 1_000.0);
+    }
+
+    #[test]
+    fn test_parse_metar_clear_sky() {
+        let info = parse_metar("UGKO 281200Z 00000KT 9999 NSC 18/12 Q1013").unwrap();
+
+        assert_eq!(info.clouds.len(), 1);
+        assert_eq!(describe_clouds(&info.clouds), "Sky clear");
+    }
+
+    #[test]
+    fn test_ceiling_is_lowest_broken_or_overcast_layer() {
+        let clouds = vec![
+            CloudLayer {
+                coverage: CloudCoverage::Few,
+                base: 1_000.0,
+            },
+            CloudLayer {
+                coverage: CloudCoverage::Broken,
+                base: 3_000.0,
+            },
+            CloudLayer {
+                coverage: CloudCoverage::Overcast,
+                base: 5_000.0,
+            },
+        ];
+
+        assert_eq!(ceiling(&clouds).unwrap().base, 3_000.0);
+    }
+
+    #[test]
+    fn test_parse_metar_variable_wind() {
+        let info = parse_metar("UGKO 281200Z VRB02KT 9999 NSC 18/12 A2992").unwrap();
+
+        assert_eq!(info.wind_dir, 0.0);
+        assert!((info.wind_speed - 2.0 / 1.94384).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_metar_negative_temperature() {
+        let info = parse_metar("UGKO 281200Z 00000KT 9999 SKC M05/M10 Q0995").unwrap();
+
+        assert_eq!(info.temperature, -5.0);
+    }
+}